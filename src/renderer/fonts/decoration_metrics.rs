@@ -0,0 +1,128 @@
+use ttf_parser::{Face, LineMetrics};
+
+use crate::renderer::CachingShaper;
+
+/// Decoration geometry read from the active font, in scaled pixels at the current font size.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct DecorationMetrics {
+    underline_position: f32,
+    underline_thickness: f32,
+    strikeout_position: f32,
+    strikeout_thickness: f32,
+}
+
+/// Converts a `post.underlineMetrics` entry (position conventionally negative, below the
+/// baseline) to scaled-pixel (position, thickness), both measured up from the baseline.
+fn underline_from_metrics(m: LineMetrics, units_per_em_scale: f32) -> (f32, f32) {
+    (
+        -m.position as f32 * units_per_em_scale,
+        m.thickness as f32 * units_per_em_scale,
+    )
+}
+
+/// Converts an `OS/2.yStrikeout*` entry (position conventionally already positive, above the
+/// baseline) to scaled-pixel (position, thickness), both measured up from the baseline.
+fn strikeout_from_metrics(m: LineMetrics, units_per_em_scale: f32) -> (f32, f32) {
+    (
+        m.position as f32 * units_per_em_scale,
+        m.thickness as f32 * units_per_em_scale,
+    )
+}
+
+impl DecorationMetrics {
+    /// Reads underline metrics from the font's own table entry, and strikeout from the OS/2
+    /// table, falling back to a synthesized position/thickness when a table entry is missing.
+    fn from_face(face: &Face, units_per_em_scale: f32, line_height: f32, descent: f32) -> Self {
+        let (underline_position, underline_thickness) = face
+            .underline_metrics()
+            .map(|m| underline_from_metrics(m, units_per_em_scale))
+            .unwrap_or((line_height / 2. - descent, 1.0));
+
+        let (strikeout_position, strikeout_thickness) = face
+            .strikeout_metrics()
+            .map(|m| strikeout_from_metrics(m, units_per_em_scale))
+            .unwrap_or((line_height / 2. - descent, underline_thickness));
+
+        DecorationMetrics {
+            underline_position,
+            underline_thickness,
+            strikeout_position,
+            strikeout_thickness,
+        }
+    }
+}
+
+impl CachingShaper {
+    /// Recomputes [`DecorationMetrics`] from the primary font face. Called whenever the active
+    /// font, its size, or the scale factor changes.
+    pub(super) fn update_decoration_metrics(&mut self) {
+        let units_per_em_scale = self.current_size() / self.primary_face().units_per_em() as f32;
+        let line_height = self.font_base_dimensions().1 as f32;
+        // `descender()` is conventionally negative (below the baseline); `from_face`'s fallback
+        // wants its magnitude.
+        let descent = -(self.primary_face().descender() as f32) * units_per_em_scale;
+
+        self.decoration_metrics = DecorationMetrics::from_face(
+            &self.primary_face(),
+            units_per_em_scale,
+            line_height,
+            descent,
+        );
+    }
+
+    /// Thickness an underline should be drawn at, in scaled pixels.
+    pub fn underline_thickness(&self) -> f32 {
+        self.decoration_metrics.underline_thickness
+    }
+
+    /// Distance from the baseline up to where a strikethrough should be drawn, in scaled pixels.
+    pub fn strikeout_position(&self) -> f32 {
+        self.decoration_metrics.strikeout_position
+    }
+
+    /// Thickness a strikethrough should be drawn at, in scaled pixels.
+    pub fn strikeout_thickness(&self) -> f32 {
+        self.decoration_metrics.strikeout_thickness
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn underline_position_is_measured_up_from_the_baseline() {
+        // post.underlinePosition is conventionally negative (below the baseline).
+        let m = LineMetrics {
+            position: -100,
+            thickness: 50,
+        };
+        let (position, thickness) = underline_from_metrics(m, 1.0);
+        assert_eq!(position, 100.0);
+        assert_eq!(thickness, 50.0);
+    }
+
+    #[test]
+    fn strikeout_position_is_not_negated() {
+        // OS/2.yStrikeoutPosition is conventionally already positive (above the baseline), unlike
+        // the underline's post table entry.
+        let m = LineMetrics {
+            position: 300,
+            thickness: 50,
+        };
+        let (position, thickness) = strikeout_from_metrics(m, 1.0);
+        assert_eq!(position, 300.0);
+        assert_eq!(thickness, 50.0);
+    }
+
+    #[test]
+    fn metrics_scale_with_units_per_em() {
+        let m = LineMetrics {
+            position: 200,
+            thickness: 100,
+        };
+        let (position, thickness) = strikeout_from_metrics(m, 0.5);
+        assert_eq!(position, 100.0);
+        assert_eq!(thickness, 50.0);
+    }
+}
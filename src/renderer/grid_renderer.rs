@@ -1,9 +1,8 @@
 use std::sync::Arc;
 
 use log::trace;
-use skia_safe::{
-    colors, dash_path_effect, BlendMode, Canvas, Color, Paint, Path, Point, Rect, HSV,
-};
+use skia_safe::{colors, BlendMode, Canvas, Color, Paint, Path, Point, Rect, HSV};
+use unicode_width::UnicodeWidthChar;
 use winit::dpi::PhysicalSize;
 
 use crate::{
@@ -16,6 +15,8 @@ use crate::{
 
 use super::fonts::font_options::FontOptions;
 
+mod custom_glyphs;
+
 pub struct GridRenderer {
     pub shaper: CachingShaper,
     pub default_style: Arc<Style>,
@@ -23,6 +24,8 @@ pub struct GridRenderer {
     pub font_dimensions: Dimensions,
     pub scale_factor: f64,
     pub is_ready: bool,
+    underline_run: Option<DecorationRun<UnderlineRunStyle>>,
+    strikethrough_run: Option<DecorationRun<Color>>,
 }
 
 /// Struct with named fields to be returned from draw_background
@@ -31,6 +34,31 @@ pub struct BackgroundInfo {
     pub transparent: bool,
 }
 
+/// The part of an underline's appearance that must match for two adjacent cells to be coalesced
+/// into a single run.
+#[derive(Clone, Copy, PartialEq)]
+struct UnderlineRunStyle {
+    kind: UnderlineStyle,
+    color: Color,
+}
+
+/// A horizontal span of matching decoration accumulated across consecutive `draw_foreground`
+/// calls on the same grid and row, drawn once over the whole span instead of once per cell.
+struct DecorationRun<S> {
+    grid_id: u64,
+    row: u64,
+    start_x: f32,
+    end_x: f32,
+    style: S,
+}
+
+/// Builds a filled, pixel-snapped decoration rectangle spanning `[x0, x1)` and centered on `y`.
+fn decoration_rect(x0: f32, x1: f32, y: f32, height: f32) -> Rect {
+    let top = (y - height / 2.).round();
+    let bottom = (y + height / 2.).round().max(top + 1.);
+    Rect::new(x0, top, x1, bottom)
+}
+
 impl GridRenderer {
     pub fn new(scale_factor: f64) -> Self {
         let mut shaper = CachingShaper::new(scale_factor as f32);
@@ -49,6 +77,8 @@ impl GridRenderer {
             font_dimensions,
             scale_factor,
             is_ready: false,
+            underline_run: None,
+            strikethrough_run: None,
         }
     }
 
@@ -162,6 +192,7 @@ impl GridRenderer {
         &mut self,
         canvas: &Canvas,
         text: &str,
+        grid_id: u64,
         grid_position: (u64, u64),
         cell_width: u64,
         style: &Option<Arc<Style>>,
@@ -180,18 +211,19 @@ impl GridRenderer {
         let region = self.compute_text_region(clip_position, cell_width + 2);
 
         if let Some(underline_style) = style.underline {
-            let line_position = self.shaper.underline_position();
-            let p1 = (
+            let color = style.special(&self.default_style.colors).to_color();
+            self.queue_underline_run(
+                canvas,
+                grid_id,
+                grid_y,
+                underline_style,
+                color,
                 x as f32,
-                (y - line_position + self.font_dimensions.height) as f32,
-            );
-            let p2 = (
                 (x + width) as f32,
-                (y - line_position + self.font_dimensions.height) as f32,
             );
-
-            self.draw_underline(canvas, style, underline_style, p1.into(), p2.into());
             drawn = true;
+        } else {
+            self.flush_underline_run(canvas);
         }
 
         canvas.save();
@@ -220,42 +252,245 @@ impl GridRenderer {
         let trimmed = trimmed.trim_end();
         let x_adjustment = leading_spaces as u64 * self.font_dimensions.width;
 
-        if !trimmed.is_empty() {
-            for blob in self
-                .shaper
-                .shape_cached(trimmed.to_string(), style.into())
-                .iter()
-            {
-                tracy_zone!("draw_text_blob");
-                canvas.draw_text_blob(
-                    blob,
-                    ((x + x_adjustment) as f32, (y + y_adjustment) as f32),
+        // Walk `trimmed` character by character, batching consecutive non-custom characters into
+        // a single `shape_cached` call and flushing that batch whenever a custom glyph (box
+        // drawing, block elements, Powerline separators) interrupts it or the text ends.
+        // `glyph_x` advances by each character's terminal column width rather than one cell per
+        // `char`, since double-width and zero-width characters don't occupy a single cell.
+        let mut glyph_x = (x + x_adjustment) as f32;
+        let mut shape_start_x = glyph_x;
+        let mut pending_shape = String::new();
+        for ch in trimmed.chars() {
+            let char_width = ch.width().unwrap_or(0) as u64;
+
+            if custom_glyphs::is_custom_glyph(ch) {
+                drawn |= self.flush_shaped_text(
+                    canvas,
+                    &mut pending_shape,
+                    shape_start_x,
+                    y,
+                    y_adjustment,
+                    style,
                     &paint,
                 );
-                drawn = true;
+
+                let glyph_region = Rect::new(
+                    glyph_x,
+                    y as f32,
+                    glyph_x + (char_width * self.font_dimensions.width) as f32,
+                    (y + self.font_dimensions.height) as f32,
+                );
+                let stroke_width = self.shaper.underline_thickness().max(1.);
+                drawn |= custom_glyphs::draw(canvas, ch, glyph_region, paint.color(), stroke_width);
+            } else {
+                pending_shape.push(ch);
+            }
+
+            glyph_x += (char_width * self.font_dimensions.width) as f32;
+            if pending_shape.is_empty() {
+                shape_start_x = glyph_x;
             }
         }
+        drawn |= self.flush_shaped_text(
+            canvas,
+            &mut pending_shape,
+            shape_start_x,
+            y,
+            y_adjustment,
+            style,
+            &paint,
+        );
+
+        // A strikethrough run spans earlier cells, so it must not be cut by this cell's clip.
+        canvas.restore();
+        canvas.save();
 
         if style.strikethrough {
-            let line_position = region.center_y();
-            paint.set_color(style.special(&self.default_style.colors).to_color());
-            canvas.draw_line(
-                (x as f32, line_position),
-                ((x + width) as f32, line_position),
-                &paint,
+            let color = style.special(&self.default_style.colors).to_color();
+            self.queue_strikethrough_run(
+                canvas,
+                grid_id,
+                grid_y,
+                color,
+                x as f32,
+                (x + width) as f32,
             );
             drawn = true;
+        } else {
+            self.flush_strikethrough_run(canvas);
         }
 
         canvas.restore();
         drawn
     }
 
+    /// Shapes and draws `pending` at `(start_x, y + y_adjustment)` if it isn't empty, clearing it
+    /// either way. Returns true if anything was drawn.
+    fn flush_shaped_text(
+        &mut self,
+        canvas: &Canvas,
+        pending: &mut String,
+        start_x: f32,
+        y: u64,
+        y_adjustment: u64,
+        style: &Style,
+        paint: &Paint,
+    ) -> bool {
+        if pending.is_empty() {
+            return false;
+        }
+
+        let mut drawn = false;
+        for blob in self
+            .shaper
+            .shape_cached(pending.clone(), style.into())
+            .iter()
+        {
+            tracy_zone!("draw_text_blob");
+            canvas.draw_text_blob(blob, (start_x, (y + y_adjustment) as f32), paint);
+            drawn = true;
+        }
+        pending.clear();
+        drawn
+    }
+
+    /// Extends the in-progress underline run if this segment continues it (same grid, row, and
+    /// style, and directly adjacent), otherwise flushes the previous run and starts a new one.
+    fn queue_underline_run(
+        &mut self,
+        canvas: &Canvas,
+        grid_id: u64,
+        row: u64,
+        kind: UnderlineStyle,
+        color: Color,
+        start_x: f32,
+        end_x: f32,
+    ) {
+        let style = UnderlineRunStyle { kind, color };
+        let contiguous = self.underline_run.as_ref().is_some_and(|run| {
+            run.grid_id == grid_id && run.row == row && run.style == style && run.end_x == start_x
+        });
+
+        if contiguous {
+            self.underline_run.as_mut().unwrap().end_x = end_x;
+        } else {
+            self.flush_underline_run(canvas);
+            self.underline_run = Some(DecorationRun {
+                grid_id,
+                row,
+                start_x,
+                end_x,
+                style,
+            });
+        }
+    }
+
+    /// Draws and clears the in-progress underline run, if any.
+    fn flush_underline_run(&mut self, canvas: &Canvas) {
+        if let Some(run) = self.underline_run.take() {
+            let (_, y) = (0, run.row) * self.font_dimensions;
+            let line_position = self.shaper.underline_position();
+            let p1 = (
+                run.start_x,
+                (y - line_position + self.font_dimensions.height) as f32,
+            );
+            let p2 = (
+                run.end_x,
+                (y - line_position + self.font_dimensions.height) as f32,
+            );
+            let thickness = self.shaper.underline_thickness();
+            self.draw_underline(
+                canvas,
+                run.style.color,
+                run.style.kind,
+                thickness,
+                p1.into(),
+                p2.into(),
+            );
+        }
+    }
+
+    /// Extends the in-progress strikethrough run if this segment continues it (same grid, row,
+    /// and color, and directly adjacent), otherwise flushes the previous run and starts a new one.
+    fn queue_strikethrough_run(
+        &mut self,
+        canvas: &Canvas,
+        grid_id: u64,
+        row: u64,
+        color: Color,
+        start_x: f32,
+        end_x: f32,
+    ) {
+        let contiguous = self.strikethrough_run.as_ref().is_some_and(|run| {
+            run.grid_id == grid_id && run.row == row && run.style == color && run.end_x == start_x
+        });
+
+        if contiguous {
+            self.strikethrough_run.as_mut().unwrap().end_x = end_x;
+        } else {
+            self.flush_strikethrough_run(canvas);
+            self.strikethrough_run = Some(DecorationRun {
+                grid_id,
+                row,
+                start_x,
+                end_x,
+                style: color,
+            });
+        }
+    }
+
+    /// Draws and clears the in-progress strikethrough run, if any.
+    fn flush_strikethrough_run(&mut self, canvas: &Canvas) {
+        if let Some(run) = self.strikethrough_run.take() {
+            let (_, y) = (0, run.row) * self.font_dimensions;
+            let baseline = (y + self.shaper.y_adjustment()) as f32;
+            let line_position = baseline - self.shaper.strikeout_position();
+            let stroke_width = self.shaper.strikeout_thickness().max(1.);
+
+            let mut paint = Paint::default();
+            paint.set_anti_alias(false);
+            paint.set_blend_mode(BlendMode::SrcOver);
+            paint.set_color(run.style);
+
+            let rect = decoration_rect(run.start_x, run.end_x, line_position, stroke_width);
+            canvas.draw_rect(rect, &paint);
+        }
+    }
+
+    /// Fills `[x0, x1)` at `y` with `segment`-wide rects separated by `gap`-wide spaces.
+    fn draw_segmented_rect(
+        &self,
+        canvas: &Canvas,
+        x0: f32,
+        x1: f32,
+        y: f32,
+        height: f32,
+        segment: f32,
+        gap: f32,
+        paint: &Paint,
+    ) {
+        let period = segment + gap;
+        let mut x = x0;
+        while x < x1 {
+            let segment_end = (x + segment).min(x1);
+            canvas.draw_rect(decoration_rect(x, segment_end, y, height), paint);
+            x += period;
+        }
+    }
+
+    /// Flushes any underline/strikethrough run still accumulated from `draw_foreground` calls.
+    /// Must be called after the grid's last cell is drawn, or the final run is never painted.
+    pub fn flush_decorations(&mut self, canvas: &Canvas) {
+        self.flush_underline_run(canvas);
+        self.flush_strikethrough_run(canvas);
+    }
+
     fn draw_underline(
         &self,
         canvas: &Canvas,
-        style: &Arc<Style>,
+        color: Color,
         underline_style: UnderlineStyle,
+        thickness: f32,
         p1: Point,
         p2: Point,
     ) {
@@ -265,37 +500,40 @@ impl GridRenderer {
         let mut underline_paint = Paint::default();
         underline_paint.set_anti_alias(false);
         underline_paint.set_blend_mode(BlendMode::SrcOver);
+        underline_paint.set_color(color);
         let underline_stroke_scale = SETTINGS.get::<RendererSettings>().underline_stroke_scale;
         // If the stroke width is less than one, clamp it to one otherwise we get nasty aliasing
         // issues
-        let stroke_width = (self.shaper.current_size() * underline_stroke_scale / 10.).max(1.);
-
-        underline_paint
-            .set_color(style.special(&self.default_style.colors).to_color())
-            .set_stroke_width(stroke_width);
+        let stroke_width = (thickness * underline_stroke_scale).max(1.);
 
         match underline_style {
             UnderlineStyle::Underline => {
-                underline_paint.set_path_effect(None);
-                canvas.draw_line(p1, p2, &underline_paint);
+                let rect = decoration_rect(p1.x, p2.x, p1.y, stroke_width);
+                canvas.draw_rect(rect, &underline_paint);
             }
             UnderlineStyle::UnderDouble => {
-                underline_paint.set_path_effect(None);
-                canvas.draw_line(p1, p2, &underline_paint);
-                let p1 = (p1.x, p1.y - 2.);
-                let p2 = (p2.x, p2.y - 2.);
-                canvas.draw_line(p1, p2, &underline_paint);
+                let rect = decoration_rect(p1.x, p2.x, p1.y, stroke_width);
+                canvas.draw_rect(rect, &underline_paint);
+                // Leave a visible gap between the two lines, scaled with the stroke width.
+                let gap = 2. * stroke_width;
+                let rect = decoration_rect(p1.x, p2.x, p1.y - gap, stroke_width);
+                canvas.draw_rect(rect, &underline_paint);
             }
             UnderlineStyle::UnderCurl => {
-                let p1 = (p1.x, p1.y - 3. + stroke_width);
-                let p2 = (p2.x, p2.y - 3. + stroke_width);
+                // Lift the curl above the baseline underline position by roughly one stroke
+                // width, clamped so thin fonts still get a visible undercurl.
+                let offset = stroke_width.max(1.);
+                let p1 = (p1.x, p1.y - offset);
+                let p2 = (p2.x, p2.y - offset);
                 underline_paint
                     .set_path_effect(None)
                     .set_anti_alias(true)
-                    .set_style(skia_safe::paint::Style::Stroke);
+                    .set_style(skia_safe::paint::Style::Stroke)
+                    .set_stroke_width(stroke_width);
                 let mut path = Path::default();
                 path.move_to(p1);
                 let mut i = p1.0;
+                // Amplitude scales with the font's underline thickness.
                 let mut sin = -2. * stroke_width;
                 let increment = self.font_dimensions.width as f32 / 2.;
                 while i < p2.0 {
@@ -306,21 +544,55 @@ impl GridRenderer {
                 canvas.draw_path(&path, &underline_paint);
             }
             UnderlineStyle::UnderDash => {
-                underline_paint.set_path_effect(dash_path_effect::new(
-                    &[6.0 * stroke_width, 2.0 * stroke_width],
-                    0.0,
-                ));
-                canvas.draw_line(p1, p2, &underline_paint);
+                self.draw_segmented_rect(
+                    canvas,
+                    p1.x,
+                    p2.x,
+                    p1.y,
+                    stroke_width,
+                    6.0 * stroke_width,
+                    2.0 * stroke_width,
+                    &underline_paint,
+                );
             }
             UnderlineStyle::UnderDot => {
-                underline_paint.set_path_effect(dash_path_effect::new(
-                    &[1.0 * stroke_width, 1.0 * stroke_width],
-                    0.0,
-                ));
-                canvas.draw_line(p1, p2, &underline_paint);
+                self.draw_segmented_rect(
+                    canvas,
+                    p1.x,
+                    p2.x,
+                    p1.y,
+                    stroke_width,
+                    stroke_width,
+                    stroke_width,
+                    &underline_paint,
+                );
             }
         }
 
         canvas.restore();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decoration_rect_has_no_gap_for_whole_pixel_height() {
+        let rect = decoration_rect(0., 10., 10., 2.);
+        assert_eq!(rect, Rect::new(0., 9., 10., 11.));
+    }
+
+    #[test]
+    fn decoration_rect_rounds_to_whole_device_pixels() {
+        let rect = decoration_rect(0., 10., 10.4, 1.3);
+        assert_eq!(rect.top, 10.);
+        assert_eq!(rect.bottom, 11.);
+    }
+
+    #[test]
+    fn decoration_rect_is_always_at_least_one_pixel_tall() {
+        let rect = decoration_rect(0., 10., 10., 0.2);
+        assert!(rect.bottom - rect.top >= 1.);
+    }
+}
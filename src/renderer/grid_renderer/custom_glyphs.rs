@@ -0,0 +1,431 @@
+//! Programmatic rendering of box-drawing, block-element, and Powerline/Nerd-Font separator
+//! glyphs.
+//!
+//! Rather than relying on the active font to ship these glyphs (and shaping them like any other
+//! text run), each one is described here as a small set of line/rect/polygon segments in
+//! normalized cell coordinates (`0.0..=1.0` across the cell's width and height, origin at the
+//! top-left). At draw time the segments are scaled to the exact `font_dimensions` cell, so rules
+//! and half-blocks tile seamlessly across adjacent cells regardless of the font or scale factor,
+//! matching the approach wezterm's custom-glyph renderer uses.
+
+use skia_safe::{Canvas, Color, Paint, Rect};
+
+/// One piece of a custom glyph, described in normalized cell coordinates.
+enum Segment {
+    /// A straight stroked line from one point to another.
+    Line((f32, f32), (f32, f32)),
+    /// An axis-aligned filled rectangle.
+    Rect(f32, f32, f32, f32),
+    /// A filled polygon, used for the triangular Powerline separators.
+    Poly(&'static [(f32, f32)]),
+}
+
+/// The style a [`Segment`] is painted with.
+#[derive(Clone, Copy)]
+enum PolyStyle {
+    Stroke,
+    Fill,
+}
+
+struct CustomGlyph {
+    segments: &'static [Segment],
+    style: PolyStyle,
+    /// Alpha the glyph is painted with, replacing the alpha of the color passed to [`draw`]; used
+    /// for the partial shade blocks (U+2591..U+2593) which are really the same full block at
+    /// different densities.
+    alpha: u8,
+    /// Multiplier applied to the caller-supplied stroke width, used to make the heavy
+    /// box-drawing variants (U+2501 etc.) visibly thicker than their light counterparts.
+    thickness_scale: f32,
+}
+
+const LIGHT_H: &[Segment] = &[Segment::Line((0.0, 0.5), (1.0, 0.5))];
+const LIGHT_V: &[Segment] = &[Segment::Line((0.5, 0.0), (0.5, 1.0))];
+const LIGHT_DOWN_RIGHT: &[Segment] = &[
+    Segment::Line((0.5, 0.5), (1.0, 0.5)),
+    Segment::Line((0.5, 0.5), (0.5, 1.0)),
+];
+const LIGHT_DOWN_LEFT: &[Segment] = &[
+    Segment::Line((0.0, 0.5), (0.5, 0.5)),
+    Segment::Line((0.5, 0.5), (0.5, 1.0)),
+];
+const LIGHT_UP_RIGHT: &[Segment] = &[
+    Segment::Line((0.5, 0.0), (0.5, 0.5)),
+    Segment::Line((0.5, 0.5), (1.0, 0.5)),
+];
+const LIGHT_UP_LEFT: &[Segment] = &[
+    Segment::Line((0.5, 0.0), (0.5, 0.5)),
+    Segment::Line((0.0, 0.5), (0.5, 0.5)),
+];
+const LIGHT_VERTICAL_RIGHT: &[Segment] = &[
+    Segment::Line((0.5, 0.0), (0.5, 1.0)),
+    Segment::Line((0.5, 0.5), (1.0, 0.5)),
+];
+const LIGHT_VERTICAL_LEFT: &[Segment] = &[
+    Segment::Line((0.5, 0.0), (0.5, 1.0)),
+    Segment::Line((0.0, 0.5), (0.5, 0.5)),
+];
+const LIGHT_HORIZONTAL_DOWN: &[Segment] = &[
+    Segment::Line((0.0, 0.5), (1.0, 0.5)),
+    Segment::Line((0.5, 0.5), (0.5, 1.0)),
+];
+const LIGHT_HORIZONTAL_UP: &[Segment] = &[
+    Segment::Line((0.0, 0.5), (1.0, 0.5)),
+    Segment::Line((0.5, 0.0), (0.5, 0.5)),
+];
+const LIGHT_CROSS: &[Segment] = &[
+    Segment::Line((0.0, 0.5), (1.0, 0.5)),
+    Segment::Line((0.5, 0.0), (0.5, 1.0)),
+];
+
+/// Half the gap between the two strands of a double line, in normalized cell coordinates.
+const DOUBLE_GAP: f32 = 0.09;
+
+const DOUBLE_H: &[Segment] = &[
+    Segment::Line((0.0, 0.5 - DOUBLE_GAP), (1.0, 0.5 - DOUBLE_GAP)),
+    Segment::Line((0.0, 0.5 + DOUBLE_GAP), (1.0, 0.5 + DOUBLE_GAP)),
+];
+const DOUBLE_V: &[Segment] = &[
+    Segment::Line((0.5 - DOUBLE_GAP, 0.0), (0.5 - DOUBLE_GAP, 1.0)),
+    Segment::Line((0.5 + DOUBLE_GAP, 0.0), (0.5 + DOUBLE_GAP, 1.0)),
+];
+const DOUBLE_DOWN_RIGHT: &[Segment] = &[
+    Segment::Line(
+        (0.5 - DOUBLE_GAP, 0.5 - DOUBLE_GAP),
+        (1.0, 0.5 - DOUBLE_GAP),
+    ),
+    Segment::Line(
+        (0.5 - DOUBLE_GAP, 0.5 - DOUBLE_GAP),
+        (0.5 - DOUBLE_GAP, 1.0),
+    ),
+    Segment::Line(
+        (0.5 + DOUBLE_GAP, 0.5 + DOUBLE_GAP),
+        (1.0, 0.5 + DOUBLE_GAP),
+    ),
+    Segment::Line(
+        (0.5 + DOUBLE_GAP, 0.5 + DOUBLE_GAP),
+        (0.5 + DOUBLE_GAP, 1.0),
+    ),
+];
+const DOUBLE_DOWN_LEFT: &[Segment] = &[
+    Segment::Line(
+        (0.0, 0.5 - DOUBLE_GAP),
+        (0.5 + DOUBLE_GAP, 0.5 - DOUBLE_GAP),
+    ),
+    Segment::Line(
+        (0.5 + DOUBLE_GAP, 0.5 - DOUBLE_GAP),
+        (0.5 + DOUBLE_GAP, 1.0),
+    ),
+    Segment::Line(
+        (0.0, 0.5 + DOUBLE_GAP),
+        (0.5 - DOUBLE_GAP, 0.5 + DOUBLE_GAP),
+    ),
+    Segment::Line(
+        (0.5 - DOUBLE_GAP, 0.5 + DOUBLE_GAP),
+        (0.5 - DOUBLE_GAP, 1.0),
+    ),
+];
+const DOUBLE_UP_RIGHT: &[Segment] = &[
+    Segment::Line(
+        (0.5 - DOUBLE_GAP, 0.0),
+        (0.5 - DOUBLE_GAP, 0.5 + DOUBLE_GAP),
+    ),
+    Segment::Line(
+        (0.5 - DOUBLE_GAP, 0.5 + DOUBLE_GAP),
+        (1.0, 0.5 + DOUBLE_GAP),
+    ),
+    Segment::Line(
+        (0.5 + DOUBLE_GAP, 0.0),
+        (0.5 + DOUBLE_GAP, 0.5 - DOUBLE_GAP),
+    ),
+    Segment::Line(
+        (0.5 + DOUBLE_GAP, 0.5 - DOUBLE_GAP),
+        (1.0, 0.5 - DOUBLE_GAP),
+    ),
+];
+const DOUBLE_UP_LEFT: &[Segment] = &[
+    Segment::Line(
+        (0.5 + DOUBLE_GAP, 0.0),
+        (0.5 + DOUBLE_GAP, 0.5 + DOUBLE_GAP),
+    ),
+    Segment::Line(
+        (0.0, 0.5 + DOUBLE_GAP),
+        (0.5 + DOUBLE_GAP, 0.5 + DOUBLE_GAP),
+    ),
+    Segment::Line(
+        (0.5 - DOUBLE_GAP, 0.0),
+        (0.5 - DOUBLE_GAP, 0.5 - DOUBLE_GAP),
+    ),
+    Segment::Line(
+        (0.0, 0.5 - DOUBLE_GAP),
+        (0.5 - DOUBLE_GAP, 0.5 - DOUBLE_GAP),
+    ),
+];
+const DOUBLE_VERTICAL_RIGHT: &[Segment] = &[
+    Segment::Line((0.5 - DOUBLE_GAP, 0.0), (0.5 - DOUBLE_GAP, 1.0)),
+    Segment::Line((0.5 + DOUBLE_GAP, 0.0), (0.5 + DOUBLE_GAP, 1.0)),
+    Segment::Line(
+        (0.5 - DOUBLE_GAP, 0.5 - DOUBLE_GAP),
+        (1.0, 0.5 - DOUBLE_GAP),
+    ),
+    Segment::Line(
+        (0.5 - DOUBLE_GAP, 0.5 + DOUBLE_GAP),
+        (1.0, 0.5 + DOUBLE_GAP),
+    ),
+];
+const DOUBLE_VERTICAL_LEFT: &[Segment] = &[
+    Segment::Line((0.5 - DOUBLE_GAP, 0.0), (0.5 - DOUBLE_GAP, 1.0)),
+    Segment::Line((0.5 + DOUBLE_GAP, 0.0), (0.5 + DOUBLE_GAP, 1.0)),
+    Segment::Line(
+        (0.0, 0.5 - DOUBLE_GAP),
+        (0.5 + DOUBLE_GAP, 0.5 - DOUBLE_GAP),
+    ),
+    Segment::Line(
+        (0.0, 0.5 + DOUBLE_GAP),
+        (0.5 + DOUBLE_GAP, 0.5 + DOUBLE_GAP),
+    ),
+];
+const DOUBLE_HORIZONTAL_DOWN: &[Segment] = &[
+    Segment::Line((0.0, 0.5 - DOUBLE_GAP), (1.0, 0.5 - DOUBLE_GAP)),
+    Segment::Line((0.0, 0.5 + DOUBLE_GAP), (1.0, 0.5 + DOUBLE_GAP)),
+    Segment::Line(
+        (0.5 - DOUBLE_GAP, 0.5 - DOUBLE_GAP),
+        (0.5 - DOUBLE_GAP, 1.0),
+    ),
+    Segment::Line(
+        (0.5 + DOUBLE_GAP, 0.5 + DOUBLE_GAP),
+        (0.5 + DOUBLE_GAP, 1.0),
+    ),
+];
+const DOUBLE_HORIZONTAL_UP: &[Segment] = &[
+    Segment::Line((0.0, 0.5 - DOUBLE_GAP), (1.0, 0.5 - DOUBLE_GAP)),
+    Segment::Line((0.0, 0.5 + DOUBLE_GAP), (1.0, 0.5 + DOUBLE_GAP)),
+    Segment::Line(
+        (0.5 - DOUBLE_GAP, 0.0),
+        (0.5 - DOUBLE_GAP, 0.5 + DOUBLE_GAP),
+    ),
+    Segment::Line(
+        (0.5 + DOUBLE_GAP, 0.0),
+        (0.5 + DOUBLE_GAP, 0.5 - DOUBLE_GAP),
+    ),
+];
+const DOUBLE_CROSS: &[Segment] = &[
+    Segment::Line((0.0, 0.5 - DOUBLE_GAP), (1.0, 0.5 - DOUBLE_GAP)),
+    Segment::Line((0.0, 0.5 + DOUBLE_GAP), (1.0, 0.5 + DOUBLE_GAP)),
+    Segment::Line((0.5 - DOUBLE_GAP, 0.0), (0.5 - DOUBLE_GAP, 1.0)),
+    Segment::Line((0.5 + DOUBLE_GAP, 0.0), (0.5 + DOUBLE_GAP, 1.0)),
+];
+
+const UPPER_HALF_BLOCK: &[Segment] = &[Segment::Rect(0.0, 0.0, 1.0, 0.5)];
+const LOWER_HALF_BLOCK: &[Segment] = &[Segment::Rect(0.0, 0.5, 1.0, 1.0)];
+const LEFT_HALF_BLOCK: &[Segment] = &[Segment::Rect(0.0, 0.0, 0.5, 1.0)];
+const RIGHT_HALF_BLOCK: &[Segment] = &[Segment::Rect(0.5, 0.0, 1.0, 1.0)];
+const FULL_BLOCK: &[Segment] = &[Segment::Rect(0.0, 0.0, 1.0, 1.0)];
+
+const POWERLINE_ARROW_RIGHT: &[Segment] = &[Segment::Poly(&[(0.0, 0.0), (1.0, 0.5), (0.0, 1.0)])];
+const POWERLINE_ARROW_LEFT: &[Segment] = &[Segment::Poly(&[(1.0, 0.0), (0.0, 0.5), (1.0, 1.0)])];
+
+/// How much thicker the heavy box-drawing variants (U+2501 etc.) are stroked than the light ones.
+const HEAVY_THICKNESS_SCALE: f32 = 2.5;
+
+fn stroked(segments: &'static [Segment]) -> CustomGlyph {
+    CustomGlyph {
+        segments,
+        style: PolyStyle::Stroke,
+        alpha: 255,
+        thickness_scale: 1.0,
+    }
+}
+
+fn stroked_heavy(segments: &'static [Segment]) -> CustomGlyph {
+    CustomGlyph {
+        segments,
+        style: PolyStyle::Stroke,
+        alpha: 255,
+        thickness_scale: HEAVY_THICKNESS_SCALE,
+    }
+}
+
+fn filled(segments: &'static [Segment]) -> CustomGlyph {
+    CustomGlyph {
+        segments,
+        style: PolyStyle::Fill,
+        alpha: 255,
+        thickness_scale: 1.0,
+    }
+}
+
+fn shaded(segments: &'static [Segment], alpha: u8) -> CustomGlyph {
+    CustomGlyph {
+        segments,
+        style: PolyStyle::Fill,
+        alpha,
+        thickness_scale: 1.0,
+    }
+}
+
+/// Looks up the custom glyph for `ch`, if any. Covers the light, heavy and double straight
+/// lines, corners, T-junctions and crosses of the box-drawing block (U+2500-U+257F), the half,
+/// full and shade block elements (U+2580-U+259F), and the Powerline solid arrows U+E0B0 and
+/// U+E0B2. Every other character in those ranges (dashed, rounded, diagonal and mixed-weight box
+/// drawing, eighth blocks, quadrants) returns `None` and is shaped through the font.
+fn lookup(ch: char) -> Option<CustomGlyph> {
+    Some(match ch {
+        '\u{2500}' => stroked(LIGHT_H),
+        '\u{2501}' => stroked_heavy(LIGHT_H),
+        '\u{2550}' => stroked(DOUBLE_H),
+        '\u{2502}' => stroked(LIGHT_V),
+        '\u{2503}' => stroked_heavy(LIGHT_V),
+        '\u{2551}' => stroked(DOUBLE_V),
+        '\u{250c}' => stroked(LIGHT_DOWN_RIGHT),
+        '\u{250f}' => stroked_heavy(LIGHT_DOWN_RIGHT),
+        '\u{2554}' => stroked(DOUBLE_DOWN_RIGHT),
+        '\u{2510}' => stroked(LIGHT_DOWN_LEFT),
+        '\u{2513}' => stroked_heavy(LIGHT_DOWN_LEFT),
+        '\u{2557}' => stroked(DOUBLE_DOWN_LEFT),
+        '\u{2514}' => stroked(LIGHT_UP_RIGHT),
+        '\u{2517}' => stroked_heavy(LIGHT_UP_RIGHT),
+        '\u{255a}' => stroked(DOUBLE_UP_RIGHT),
+        '\u{2518}' => stroked(LIGHT_UP_LEFT),
+        '\u{251b}' => stroked_heavy(LIGHT_UP_LEFT),
+        '\u{255d}' => stroked(DOUBLE_UP_LEFT),
+        '\u{251c}' => stroked(LIGHT_VERTICAL_RIGHT),
+        '\u{2523}' => stroked_heavy(LIGHT_VERTICAL_RIGHT),
+        '\u{2560}' => stroked(DOUBLE_VERTICAL_RIGHT),
+        '\u{2524}' => stroked(LIGHT_VERTICAL_LEFT),
+        '\u{252b}' => stroked_heavy(LIGHT_VERTICAL_LEFT),
+        '\u{2563}' => stroked(DOUBLE_VERTICAL_LEFT),
+        '\u{252c}' => stroked(LIGHT_HORIZONTAL_DOWN),
+        '\u{2533}' => stroked_heavy(LIGHT_HORIZONTAL_DOWN),
+        '\u{2566}' => stroked(DOUBLE_HORIZONTAL_DOWN),
+        '\u{2534}' => stroked(LIGHT_HORIZONTAL_UP),
+        '\u{253b}' => stroked_heavy(LIGHT_HORIZONTAL_UP),
+        '\u{2569}' => stroked(DOUBLE_HORIZONTAL_UP),
+        '\u{253c}' => stroked(LIGHT_CROSS),
+        '\u{254b}' => stroked_heavy(LIGHT_CROSS),
+        '\u{256c}' => stroked(DOUBLE_CROSS),
+
+        '\u{2580}' => filled(UPPER_HALF_BLOCK),
+        '\u{2584}' => filled(LOWER_HALF_BLOCK),
+        '\u{2588}' => filled(FULL_BLOCK),
+        '\u{258c}' => filled(LEFT_HALF_BLOCK),
+        '\u{2590}' => filled(RIGHT_HALF_BLOCK),
+        '\u{2591}' => shaded(FULL_BLOCK, 64),
+        '\u{2592}' => shaded(FULL_BLOCK, 128),
+        '\u{2593}' => shaded(FULL_BLOCK, 192),
+
+        '\u{e0b0}' | '\u{e0b2}' => {
+            if ch == '\u{e0b0}' {
+                filled(POWERLINE_ARROW_RIGHT)
+            } else {
+                filled(POWERLINE_ARROW_LEFT)
+            }
+        }
+
+        _ => return None,
+    })
+}
+
+/// Returns true if `ch` would be drawn by [`draw`] instead of being shaped through the font.
+pub fn is_custom_glyph(ch: char) -> bool {
+    lookup(ch).is_some()
+}
+
+/// Draws the custom glyph for `ch` into `region`, scaling its normalized segments to the exact
+/// cell size. Returns `false` (drawing nothing) if `ch` isn't a custom glyph, so the caller can
+/// fall back to shaping it through the font.
+pub fn draw(canvas: &Canvas, ch: char, region: Rect, color: Color, stroke_width: f32) -> bool {
+    let Some(glyph) = lookup(ch) else {
+        return false;
+    };
+
+    let mut paint = Paint::default();
+    paint.set_anti_alias(true);
+    paint.set_color(color);
+    paint.set_alpha(glyph.alpha);
+    match glyph.style {
+        PolyStyle::Stroke => {
+            paint.set_style(skia_safe::paint::Style::Stroke);
+            paint.set_stroke_width(stroke_width * glyph.thickness_scale);
+        }
+        PolyStyle::Fill => paint.set_style(skia_safe::paint::Style::Fill),
+    };
+
+    let width = region.width();
+    let height = region.height();
+    let scale = |(nx, ny): (f32, f32)| (region.left + nx * width, region.top + ny * height);
+
+    for segment in glyph.segments {
+        match segment {
+            Segment::Line(p1, p2) => {
+                canvas.draw_line(scale(*p1), scale(*p2), &paint);
+            }
+            Segment::Rect(x0, y0, x1, y1) => {
+                let (l, t) = scale((*x0, *y0));
+                let (r, b) = scale((*x1, *y1));
+                canvas.draw_rect(Rect::new(l, t, r, b), &paint);
+            }
+            Segment::Poly(points) => {
+                let mut path = skia_safe::Path::default();
+                let mut points = points.iter().map(|p| scale(*p));
+                if let Some(first) = points.next() {
+                    path.move_to(first);
+                    for point in points {
+                        path.line_to(point);
+                    }
+                    path.close();
+                }
+                canvas.draw_path(&path, &paint);
+            }
+        }
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_custom_glyph_covers_box_drawing_block_and_block_elements() {
+        assert!(is_custom_glyph('\u{2500}'));
+        assert!(is_custom_glyph('\u{256c}'));
+        assert!(is_custom_glyph('\u{2588}'));
+    }
+
+    #[test]
+    fn is_custom_glyph_covers_powerline_separators() {
+        assert!(is_custom_glyph('\u{e0b0}'));
+        assert!(is_custom_glyph('\u{e0b2}'));
+    }
+
+    #[test]
+    fn is_custom_glyph_rejects_ordinary_text() {
+        assert!(!is_custom_glyph('a'));
+        assert!(!is_custom_glyph(' '));
+    }
+
+    #[test]
+    fn heavy_box_drawing_glyphs_are_thicker_than_their_light_counterparts() {
+        let light = lookup('\u{2500}').unwrap();
+        let heavy = lookup('\u{2501}').unwrap();
+        assert_eq!(light.thickness_scale, 1.0);
+        assert_eq!(heavy.thickness_scale, HEAVY_THICKNESS_SCALE);
+    }
+
+    #[test]
+    fn double_box_drawing_glyphs_have_two_strands() {
+        let double_h = lookup('\u{2550}').unwrap();
+        assert_eq!(double_h.segments.len(), 2);
+    }
+
+    #[test]
+    fn shaded_blocks_scale_down_from_a_full_block() {
+        let light_shade = lookup('\u{2591}').unwrap();
+        let medium_shade = lookup('\u{2592}').unwrap();
+        let dark_shade = lookup('\u{2593}').unwrap();
+        assert!(light_shade.alpha < medium_shade.alpha);
+        assert!(medium_shade.alpha < dark_shade.alpha);
+    }
+}